@@ -8,6 +8,9 @@ use bevy_ecs::{
 
 use crate::{zip_exact, TraitImplMeta, TraitImplRegistry, TraitQuery};
 
+#[cfg(feature = "track_change_detection")]
+use std::panic::Location;
+
 /// Read-access to all components implementing a trait for a given entity.
 ///
 /// This supports change detection and detection for added objects via
@@ -30,9 +33,60 @@ pub struct ReadTraits<'a, Trait: ?Sized + TraitQuery> {
     pub(crate) this_run: Tick,
 }
 
+// Deliberately not `ExactSizeIterator`: not every registered component is guaranteed to
+// be present on a given entity, so `self.components.len()` is only an upper bound, not
+// an exact count. Use [`ReadTraits::len`] for that upper bound when pre-sizing a
+// collection; implementing `ExactSizeIterator` here would make `len()` lie.
+//
+// Deliberately not `std::iter::Chain<ReadTableTraitsIter, ReadSparseTraitsIter>`: `Chain::nth`
+// doesn't delegate to the first iterator's `nth` at all, it drains it via repeated `next()`
+// calls (full `Ref` construction, tick derefs, the works) and only calls `.nth()` on the
+// second iterator for whatever index remains. That would silently defeat the whole point of
+// `ReadTableTraitsIter::nth`/`ReadSparseTraitsIter::nth` (skip matching components without
+// building `Ref`s) for the only way callers can actually reach them, via `ReadTraits::iter`.
 #[doc(hidden)]
-pub type CombinedReadTraitsIter<'a, Trait> =
-    std::iter::Chain<ReadTableTraitsIter<'a, Trait>, ReadSparseTraitsIter<'a, Trait>>;
+pub struct CombinedReadTraitsIter<'a, Trait: ?Sized> {
+    table: ReadTableTraitsIter<'a, Trait>,
+    sparse: ReadSparseTraitsIter<'a, Trait>,
+}
+
+impl<'a, Trait: ?Sized + TraitQuery> Iterator for CombinedReadTraitsIter<'a, Trait> {
+    type Item = Ref<'a, Trait>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.table.next().or_else(|| self.sparse.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (table_lo, table_hi) = self.table.size_hint();
+        let (sparse_lo, sparse_hi) = self.sparse.size_hint();
+        (
+            table_lo + sparse_lo,
+            table_hi.zip(sparse_hi).map(|(a, b)| a + b),
+        )
+    }
+
+    fn count(self) -> usize {
+        self.table.count() + self.sparse.count()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        // Prefer the sparse segment's last match, since it comes after the table
+        // segment; only fall back to the table's last match if sparse has none.
+        self.sparse.last().or_else(|| self.table.last())
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        // Advance past `n` matches in the table segment first, without constructing
+        // `Ref`s for them, tracking how many were actually found so we know how far
+        // to continue into the sparse segment once the table segment runs dry.
+        let table_matches = self.table.advance_matches(n);
+        if table_matches < n {
+            return self.sparse.nth(n - table_matches);
+        }
+        self.table.next().or_else(|| self.sparse.nth(0))
+    }
+}
 
 #[doc(hidden)]
 pub struct ReadTableTraitsIter<'a, Trait: ?Sized> {
@@ -89,6 +143,92 @@ impl<'a, Trait: ?Sized + TraitQuery> Iterator for ReadTableTraitsIter<'a, Trait>
             changed_by
         ))
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.components.len()))
+    }
+
+    fn count(self) -> usize {
+        // Avoid constructing `Ref`s: just check which of the remaining
+        // registered components are actually present in the table.
+        unsafe { zip_exact(self.components, self.meta) }
+            .filter(|(&component, _)| self.table.get_component(component, self.table_row).is_some())
+            .count()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        // Scan from the end of the registered list so we only ever construct a `Ref`
+        // for the actual last match, instead of exhausting `next()` and discarding
+        // every `Ref` but the final one.
+        let (ptr, component, meta) = unsafe { zip_exact(self.components.rev(), self.meta.rev()) }
+            .find_map(|(&component, meta)| {
+                // SAFETY: we know that the `table_row` is a valid index.
+                let ptr = unsafe { self.table.get_component(component, self.table_row) }?;
+                Some((ptr, component, meta))
+            })?;
+        let trait_object = unsafe { meta.dyn_ctor.cast(ptr) };
+
+        // SAFETY:
+        // Read access has been registered, so we can dereference it immutably.
+        let added_tick = unsafe {
+            self.table
+                .get_added_tick(component, self.table_row)?
+                .deref()
+        };
+        let changed_tick = unsafe {
+            self.table
+                .get_changed_tick(component, self.table_row)?
+                .deref()
+        };
+        let changed_by = unsafe {
+            self.table
+                .get_changed_by(component, self.table_row)?
+                .deref()
+        };
+
+        Some(Ref::new(
+            trait_object,
+            added_tick,
+            changed_tick,
+            self.last_run,
+            self.this_run,
+
+            #[cfg(feature = "track_change_detection")]
+            changed_by,
+        ))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        // Skip the first `n` matching components without constructing `Ref`s
+        // for them, i.e. without the `meta.dyn_ctor.cast` + tick derefs.
+        if self.advance_matches(n) < n {
+            return None;
+        }
+        self.next()
+    }
+}
+
+impl<'a, Trait: ?Sized + TraitQuery> ReadTableTraitsIter<'a, Trait> {
+    /// Advances past up to `n` matching components without constructing `Ref`s for
+    /// them, returning how many were actually found (less than `n` if this iterator
+    /// ran out of registered components first). Shared by [`Self::nth`] and
+    /// [`CombinedReadTraitsIter::nth`], which needs the count to know how far to
+    /// continue into the sparse segment once the table segment is exhausted.
+    fn advance_matches(&mut self, n: usize) -> usize {
+        for found in 0..n {
+            let matched = unsafe { zip_exact(&mut self.components, &mut self.meta) }.find(
+                |&(&component, _)| {
+                    // SAFETY: we know that the `table_row` is a valid index.
+                    unsafe { self.table.get_component(component, self.table_row) }.is_some()
+                },
+            );
+            if matched.is_none() {
+                return found;
+            }
+        }
+        n
+    }
 }
 
 #[doc(hidden)]
@@ -129,6 +269,146 @@ impl<'a, Trait: ?Sized + TraitQuery> Iterator for ReadSparseTraitsIter<'a, Trait
             changed_by
         ))
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.components.len()))
+    }
+
+    fn count(self) -> usize {
+        // Avoid constructing `Ref`s: just check which of the remaining
+        // registered components are actually present for this entity.
+        unsafe { zip_exact(self.components, self.meta) }
+            .filter(|(&component, _)| {
+                self.sparse_sets
+                    .get(component)
+                    .is_some_and(|set| set.get(self.entity).is_some())
+            })
+            .count()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        // Scan from the end of the registered list so we only ever construct a `Ref`
+        // for the actual last match, instead of exhausting `next()` and discarding
+        // every `Ref` but the final one.
+        let (ptr, ticks_ptr, meta, changed_by) =
+            unsafe { zip_exact(self.components.rev(), self.meta.rev()) }.find_map(
+                |(&component, meta)| {
+                    let set = self.sparse_sets.get(component)?;
+                    let (ptr, ticks, changed_by) = set.get_with_ticks(self.entity)?;
+                    Some((ptr, ticks, meta, changed_by))
+                },
+            )?;
+        let trait_object = unsafe { meta.dyn_ctor.cast(ptr) };
+        let added_tick = unsafe { ticks_ptr.added.deref() };
+        let changed_tick = unsafe { ticks_ptr.changed.deref() };
+        let changed_by = unsafe { changed_by.deref() };
+        Some(Ref::new(
+            trait_object,
+            added_tick,
+            changed_tick,
+            self.last_run,
+            self.this_run,
+
+            #[cfg(feature = "track_change_detection")]
+            changed_by,
+        ))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        // Skip the first `n` matching components without constructing `Ref`s
+        // for them, i.e. without the `meta.dyn_ctor.cast` + tick derefs.
+        if self.advance_matches(n) < n {
+            return None;
+        }
+        self.next()
+    }
+}
+
+impl<'a, Trait: ?Sized + TraitQuery> ReadSparseTraitsIter<'a, Trait> {
+    /// Advances past up to `n` matching components without constructing `Ref`s for
+    /// them, returning how many were actually found (less than `n` if this iterator
+    /// ran out of registered components first). Used by [`Self::nth`], which
+    /// [`CombinedReadTraitsIter::nth`] calls for the sparse segment.
+    fn advance_matches(&mut self, n: usize) -> usize {
+        for found in 0..n {
+            let matched = unsafe { zip_exact(&mut self.components, &mut self.meta) }.find(
+                |&(&component, _)| {
+                    self.sparse_sets
+                        .get(component)
+                        .is_some_and(|set| set.get(self.entity).is_some())
+                },
+            );
+            if matched.is_none() {
+                return found;
+            }
+        }
+        n
+    }
+}
+
+#[doc(hidden)]
+pub type CombinedReadTraitsIterUnwrapped<'a, Trait> =
+    std::iter::Chain<ReadTableTraitsIterUnwrapped<'a, Trait>, ReadSparseTraitsIterUnwrapped<'a, Trait>>;
+
+/// Like [`ReadTableTraitsIter`], but skips fetching the added/changed ticks entirely,
+/// for callers that only want the trait objects via [`ReadTraits::iter_unwrapped`].
+#[doc(hidden)]
+pub struct ReadTableTraitsIterUnwrapped<'a, Trait: ?Sized> {
+    pub(crate) components: std::slice::Iter<'a, ComponentId>,
+    pub(crate) meta: std::slice::Iter<'a, TraitImplMeta<Trait>>,
+    pub(crate) table_row: TableRow,
+    pub(crate) table: &'a Table,
+}
+
+impl<'a, Trait: ?Sized + TraitQuery> Iterator for ReadTableTraitsIterUnwrapped<'a, Trait> {
+    type Item = &'a Trait;
+    fn next(&mut self) -> Option<Self::Item> {
+        // Iterate the remaining table components that are registered,
+        // until we find one that exists in the table.
+        let (ptr, meta) = unsafe { zip_exact(&mut self.components, &mut self.meta) }
+            .find_map(|(&component, meta)| {
+                // SAFETY: we know that the `table_row` is a valid index.
+                let ptr = unsafe { self.table.get_component(component, self.table_row) }?;
+                Some((ptr, meta))
+            })?;
+        Some(unsafe { meta.dyn_ctor.cast(ptr) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.components.len()))
+    }
+}
+
+/// Like [`ReadSparseTraitsIter`], but skips fetching the added/changed ticks entirely,
+/// for callers that only want the trait objects via [`ReadTraits::iter_unwrapped`].
+#[doc(hidden)]
+pub struct ReadSparseTraitsIterUnwrapped<'a, Trait: ?Sized> {
+    pub(crate) components: std::slice::Iter<'a, ComponentId>,
+    pub(crate) meta: std::slice::Iter<'a, TraitImplMeta<Trait>>,
+    pub(crate) entity: Entity,
+    pub(crate) sparse_sets: &'a SparseSets,
+}
+
+impl<'a, Trait: ?Sized + TraitQuery> Iterator for ReadSparseTraitsIterUnwrapped<'a, Trait> {
+    type Item = &'a Trait;
+    fn next(&mut self) -> Option<Self::Item> {
+        // Iterate the remaining sparse set components that are registered,
+        // until we find one that exists in the archetype.
+        let (ptr, meta) = unsafe { zip_exact(&mut self.components, &mut self.meta) }
+            .find_map(|(&component, meta)| {
+                let set = self.sparse_sets.get(component)?;
+                let ptr = set.get(self.entity)?;
+                Some((ptr, meta))
+            })?;
+        Some(unsafe { meta.dyn_ctor.cast(ptr) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.components.len()))
+    }
 }
 
 impl<'w, Trait: ?Sized + TraitQuery> IntoIterator for ReadTraits<'w, Trait> {
@@ -152,7 +432,7 @@ impl<'w, Trait: ?Sized + TraitQuery> IntoIterator for ReadTraits<'w, Trait> {
             last_run: self.last_run,
             this_run: self.this_run,
         };
-        table.chain(sparse)
+        CombinedReadTraitsIter { table, sparse }
     }
 }
 
@@ -177,7 +457,7 @@ impl<'w, Trait: ?Sized + TraitQuery> IntoIterator for &ReadTraits<'w, Trait> {
             last_run: self.last_run,
             this_run: self.this_run,
         };
-        table.chain(sparse)
+        CombinedReadTraitsIter { table, sparse }
     }
 }
 
@@ -198,4 +478,58 @@ impl<'w, Trait: ?Sized + TraitQuery> ReadTraits<'w, Trait> {
     pub fn iter_changed(&self) -> impl Iterator<Item = Ref<'w, Trait>> {
         self.iter().filter(DetectChanges::is_changed)
     }
+
+    /// Returns an iterator over the components implementing `Trait` for the current entity
+    /// whose values were changed since the last time the system was run, paired with the
+    /// [`Location`] of the code that last mutated each one.
+    ///
+    /// This lets debugging/tooling systems report which system+file:line last mutated each
+    /// matched trait implementation on an entity.
+    #[cfg(feature = "track_change_detection")]
+    pub fn iter_changed_with_caller(
+        &self,
+    ) -> impl Iterator<Item = (Ref<'w, Trait>, &'static Location<'static>)> {
+        self.iter_changed().map(|r| {
+            let location = r.changed_by();
+            (r, location)
+        })
+    }
+
+    /// Returns an iterator over the components implementing `Trait` for the current entity,
+    /// without any change-detection information.
+    ///
+    /// Unlike [`ReadTraits::iter`], this skips fetching the added/changed ticks entirely,
+    /// which is a meaningful speedup for read-heavy systems (dispatch, rendering collection)
+    /// that don't need change detection.
+    pub fn iter_unwrapped(&self) -> CombinedReadTraitsIterUnwrapped<'w, Trait> {
+        let table = ReadTableTraitsIterUnwrapped {
+            components: self.registry.table_components.iter(),
+            meta: self.registry.table_meta.iter(),
+            table: self.table,
+            table_row: self.table_row,
+        };
+        let sparse = ReadSparseTraitsIterUnwrapped {
+            components: self.registry.sparse_components.iter(),
+            meta: self.registry.sparse_meta.iter(),
+            entity: self.table.entities()[self.table_row.as_usize()],
+            sparse_sets: self.sparse_sets,
+        };
+        table.chain(sparse)
+    }
+
+    /// Returns a cheap upper bound on the number of components implementing `Trait`
+    /// that this entity could have, for pre-sizing collections built from [`ReadTraits::iter`].
+    ///
+    /// This is *not* the exact number of matched trait impls: not every registered
+    /// component is guaranteed to be present on the entity.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.registry.table_components.len() + self.registry.sparse_components.len()
+    }
+
+    /// Returns `true` if no component implementing `Trait` is registered at all.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
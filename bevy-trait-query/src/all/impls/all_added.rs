@@ -0,0 +1,167 @@
+use bevy_ecs::ptr::UnsafeCellDeref;
+use std::marker::PhantomData;
+
+use bevy_ecs::{
+    archetype::Archetype,
+    component::{ComponentId, Components, Tick},
+    prelude::{Entity, World},
+    query::{FilteredAccess, QueryData, QueryFilter, ReadOnlyQueryData, WorldQuery},
+    storage::{Table, TableRow},
+    world::unsafe_world_cell::UnsafeWorldCell,
+};
+
+use crate::{debug_unreachable, TraitQuery, TraitQueryState};
+
+use crate::all::impls::all_changed::AllTraitsChangeDetectionFetch;
+
+/// [`WorldQuery`] filter for entities with [any](crate::All) component implementing a
+/// trait that was added since the last time the system ran.
+pub struct AllAdded<Trait: ?Sized + TraitQuery> {
+    marker: PhantomData<&'static Trait>,
+}
+
+unsafe impl<Trait: ?Sized + TraitQuery> QueryData for AllAdded<Trait> {
+    type ReadOnly = Self;
+
+    /// SAFETY: read-only access
+    const IS_READ_ONLY: bool = true;
+
+    type Item<'w> = bool;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+        item
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Self::Item<'w> {
+        unsafe {
+            let table = fetch.table.unwrap_or_else(|| debug_unreachable());
+            for &component in &*fetch.components {
+                // Check whichever storage this component lives in for this archetype;
+                // components the entity lacks altogether have no tick to check.
+                let tick = if let Some(tick) = table.get_added_tick(component, table_row) {
+                    Some(tick)
+                } else if let Some(set) = fetch.sparse_sets.get(component) {
+                    set.get_added_tick(entity)
+                } else {
+                    None
+                };
+                if let Some(tick) = tick {
+                    if tick.deref().is_newer_than(fetch.last_run, fetch.this_run) {
+                        return true;
+                    }
+                }
+            }
+            false
+        }
+    }
+}
+
+unsafe impl<Trait: ?Sized + TraitQuery> WorldQuery for AllAdded<Trait> {
+    type Fetch<'w> = AllTraitsChangeDetectionFetch<'w>;
+    type State = TraitQueryState<Trait>;
+
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &Self::State,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        unsafe {
+            Self::Fetch::<'w> {
+                components: state.components.clone(),
+                table: None,
+                sparse_sets: &world.storages().sparse_sets,
+                last_run,
+                this_run,
+            }
+        }
+    }
+
+    // As with `OneChanged`, we don't know at compile time whether the components our
+    // trait has been impl'd for are stored in table or in sparse set.
+    const IS_DENSE: bool = false;
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        _state: &Self::State,
+        _archetype: &'w Archetype,
+        table: &'w Table,
+    ) {
+        fetch.table = Some(table);
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(_fetch: &mut Self::Fetch<'w>, _state: &Self::State, _table: &'w Table) {
+        unsafe {
+            // only gets called if IS_DENSE == true, which does not hold for us
+            debug_unreachable()
+        }
+    }
+
+    #[inline]
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
+        // Unlike `OneChanged`, any number of the registered components may be present
+        // and changed/added on a matching entity at once, so every one of them is
+        // actually read by `fetch` and needs read-access registered unconditionally
+        // (no OR-of-exactly-one collapsing, and no archetype `with` filtering).
+        for &component in &*state.components {
+            assert!(
+                !access.access().has_component_write(component),
+                "&{} conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
+                std::any::type_name::<Trait>(),
+            );
+            access.access_mut().add_component_read(component);
+        }
+    }
+
+    #[inline]
+    fn init_state(world: &mut World) -> Self::State {
+        TraitQueryState::init(world)
+    }
+
+    #[inline]
+    fn get_state(_: &Components) -> Option<Self::State> {
+        // TODO: fix this https://github.com/bevyengine/bevy/issues/13798
+        panic!(
+            "transmuting and any other operations concerning the state of a query are currently broken and shouldn't be used. See https://github.com/JoJoJet/bevy-trait-query/issues/59"
+        );
+    }
+
+    fn matches_component_set(
+        state: &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        // Unlike `One`/`OneChanged`, which require exactly one registered component to be
+        // present, `AllAdded` must also match archetypes with two or more components
+        // implementing `Trait` present at once — that's the whole point of the request
+        // ("yield true when *any* component... was added"). So this is an OR over every
+        // registered component rather than `matches_component_set_one`'s exactly-one
+        // check, but it still guarantees at least one is present: unconditional `true`
+        // would run `fetch`/`filter_fetch` against archetypes with zero components
+        // implementing `Trait` at all, which breaks that invariant.
+        state.components.iter().any(|&id| set_contains_id(id))
+    }
+
+    #[inline]
+    fn shrink_fetch<'wlong: 'wshort, 'wshort>(fetch: Self::Fetch<'wlong>) -> Self::Fetch<'wshort> {
+        fetch
+    }
+}
+
+unsafe impl<Trait: ?Sized + TraitQuery> ReadOnlyQueryData for AllAdded<Trait> {}
+unsafe impl<Trait: ?Sized + TraitQuery> QueryFilter for AllAdded<Trait> {
+    const IS_ARCHETYPAL: bool = false;
+    unsafe fn filter_fetch(
+        fetch: &mut Self::Fetch<'_>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> bool {
+        unsafe { <Self as QueryData>::fetch(fetch, entity, table_row) }
+    }
+}
@@ -0,0 +1,272 @@
+use std::marker::PhantomData;
+
+use bevy_ecs::{
+    archetype::Archetype,
+    bundle::Bundle,
+    component::{ComponentId, Components, Tick},
+    prelude::{Entity, World},
+    query::{FilteredAccess, QueryData, ReadOnlyQueryData, WorldQuery},
+    storage::{SparseSets, Table, TableRow},
+    world::unsafe_world_cell::UnsafeWorldCell,
+};
+
+use crate::all::core::read::{ReadSparseTraitsIter, ReadTableTraitsIter};
+use crate::{zip_exact, TraitImplMeta, TraitImplRegistry, TraitQuery, TraitQueryState};
+
+/// Read-access to all components implementing a trait for a given entity,
+/// except for a statically specified set of concrete component types.
+///
+/// This is useful when a second query in the same system holds exclusive access to a
+/// concrete component that also implements `Trait`: querying `All<&dyn Trait>` for such
+/// an entity would alias and panic in `update_component_access`, whereas excluding the
+/// conflicting type here resolves it without having to split the two into separate systems.
+pub struct AllExceptTraits<'a, Trait: ?Sized + TraitQuery> {
+    table: &'a Table,
+    table_row: TableRow,
+    sparse_sets: &'a SparseSets,
+    last_run: Tick,
+    this_run: Tick,
+    // Registered components with the excluded set already filtered out.
+    table_components: &'a [ComponentId],
+    table_meta: &'a [TraitImplMeta<Trait>],
+    sparse_components: &'a [ComponentId],
+    sparse_meta: &'a [TraitImplMeta<Trait>],
+}
+
+impl<'a, Trait: ?Sized + TraitQuery> AllExceptTraits<'a, Trait> {
+    /// Returns an iterator over the components implementing `Trait` for the current entity,
+    /// skipping any component that was excluded from this query.
+    pub fn iter(&self) -> impl Iterator<Item = bevy_ecs::change_detection::Ref<'a, Trait>> {
+        let table = ReadTableTraitsIter {
+            components: self.table_components.iter(),
+            meta: self.table_meta.iter(),
+            table: self.table,
+            table_row: self.table_row,
+            last_run: self.last_run,
+            this_run: self.this_run,
+        };
+        let sparse = ReadSparseTraitsIter {
+            components: self.sparse_components.iter(),
+            meta: self.sparse_meta.iter(),
+            entity: self.table.entities()[self.table_row.as_usize()],
+            sparse_sets: self.sparse_sets,
+            last_run: self.last_run,
+            this_run: self.this_run,
+        };
+        table.chain(sparse)
+    }
+}
+
+#[doc(hidden)]
+pub struct AllExceptFetch<'w, Trait: ?Sized + TraitQuery> {
+    registry: &'w TraitImplRegistry<Trait>,
+    excluded: Box<[ComponentId]>,
+    table: Option<&'w Table>,
+    sparse_sets: &'w SparseSets,
+    // Filtered copies of the registry's component/meta lists for the current archetype,
+    // built once in `set_archetype` so that `fetch` never has to re-check the exclusion set.
+    table_components: Box<[ComponentId]>,
+    table_meta: Box<[TraitImplMeta<Trait>]>,
+    sparse_components: Box<[ComponentId]>,
+    sparse_meta: Box<[TraitImplMeta<Trait>]>,
+    last_run: Tick,
+    this_run: Tick,
+}
+
+/// [`WorldQuery`] state for [`AllExcept`]: the full list of components registered for
+/// `Trait` (reusing [`TraitQueryState`]) together with the [`ComponentId`]s of the
+/// concrete types excluded from iteration.
+pub struct AllExceptState<Trait: ?Sized + TraitQuery, B: Bundle> {
+    inner: TraitQueryState<Trait>,
+    excluded: Box<[ComponentId]>,
+    marker: PhantomData<B>,
+}
+
+/// [`WorldQuery`] that reads all components implementing `Trait` for an entity,
+/// except for the concrete component types named in `B`.
+///
+/// ```ignore
+/// fn system(query: Query<AllExcept<dyn Trait, (Velocity, Transform)>>) { .. }
+/// ```
+///
+/// This is the trait-query equivalent of `EntityRefExcept<B>`.
+pub struct AllExcept<Trait: ?Sized + TraitQuery, B: Bundle> {
+    marker: PhantomData<(fn() -> Box<Trait>, B)>,
+}
+
+unsafe impl<Trait: ?Sized + TraitQuery, B: Bundle> QueryData for AllExcept<Trait, B> {
+    type ReadOnly = Self;
+
+    const IS_READ_ONLY: bool = true;
+
+    type Item<'w> = AllExceptTraits<'w, Trait>;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+        item
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        _entity: Entity,
+        table_row: TableRow,
+    ) -> Self::Item<'w> {
+        unsafe {
+            let table = fetch.table.unwrap_or_else(|| crate::debug_unreachable());
+            AllExceptTraits {
+                table,
+                table_row,
+                sparse_sets: fetch.sparse_sets,
+                last_run: fetch.last_run,
+                this_run: fetch.this_run,
+                table_components: &fetch.table_components,
+                table_meta: &fetch.table_meta,
+                sparse_components: &fetch.sparse_components,
+                sparse_meta: &fetch.sparse_meta,
+            }
+        }
+    }
+}
+
+unsafe impl<Trait: ?Sized + TraitQuery, B: Bundle> WorldQuery for AllExcept<Trait, B> {
+    type Fetch<'w> = AllExceptFetch<'w, Trait>;
+    type State = AllExceptState<Trait, B>;
+
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &Self::State,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        unsafe {
+            // SAFETY: the registry is only ever accessed immutably by trait queries.
+            let registry = world
+                .get_resource::<TraitImplRegistry<Trait>>()
+                .unwrap_or_else(|| crate::debug_unreachable());
+            Self::Fetch::<'w> {
+                registry,
+                excluded: state.excluded.clone(),
+                table: None,
+                sparse_sets: &world.storages().sparse_sets,
+                table_components: Box::new([]),
+                table_meta: Box::new([]),
+                sparse_components: Box::new([]),
+                sparse_meta: Box::new([]),
+                last_run,
+                this_run,
+            }
+        }
+    }
+
+    // As with `All<&dyn Trait>`, we don't know at compile time whether the components our
+    // trait has been impl'd for are stored in table or in sparse set.
+    const IS_DENSE: bool = false;
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        _state: &Self::State,
+        _archetype: &'w Archetype,
+        table: &'w Table,
+    ) {
+        fetch.table = Some(table);
+
+        let excluded = &fetch.excluded;
+        let filter = |(&component, _): &(&ComponentId, &TraitImplMeta<Trait>)| {
+            !excluded.contains(&component)
+        };
+        fetch.table_components = zip_exact(
+            fetch.registry.table_components.iter(),
+            fetch.registry.table_meta.iter(),
+        )
+        .filter(filter)
+        .map(|(&component, _)| component)
+        .collect();
+        fetch.table_meta = zip_exact(
+            fetch.registry.table_components.iter(),
+            fetch.registry.table_meta.iter(),
+        )
+        .filter(filter)
+        .map(|(_, meta)| meta.clone())
+        .collect();
+        fetch.sparse_components = zip_exact(
+            fetch.registry.sparse_components.iter(),
+            fetch.registry.sparse_meta.iter(),
+        )
+        .filter(filter)
+        .map(|(&component, _)| component)
+        .collect();
+        fetch.sparse_meta = zip_exact(
+            fetch.registry.sparse_components.iter(),
+            fetch.registry.sparse_meta.iter(),
+        )
+        .filter(filter)
+        .map(|(_, meta)| meta.clone())
+        .collect();
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(_fetch: &mut Self::Fetch<'w>, _state: &Self::State, _table: &'w Table) {
+        unsafe {
+            // only gets called if IS_DENSE == true, which does not hold for us
+            crate::debug_unreachable()
+        }
+    }
+
+    #[inline]
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
+        // Excluded components are skipped entirely, since the whole point of `AllExcept`
+        // is to avoid aliasing with a concurrent exclusive borrow of one of them (unlike
+        // `All<&dyn Trait>`, which would register read-access for every registered
+        // component and panic via `has_component_write` in that case). Every other
+        // registered component is still actually read by `fetch`, though, so it keeps
+        // the same write-conflict assert as `All`/`AllChanged`/`AllAdded`.
+        for &component in &*state.inner.components {
+            if state.excluded.contains(&component) {
+                continue;
+            }
+            assert!(
+                !access.access().has_component_write(component),
+                "&{} conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
+                std::any::type_name::<Trait>(),
+            );
+            access.access_mut().add_component_read(component);
+        }
+    }
+
+    #[inline]
+    fn init_state(world: &mut World) -> Self::State {
+        let inner = TraitQueryState::init(world);
+        let mut excluded = Vec::new();
+        B::component_ids(world.components_registrator(), &mut |id| excluded.push(id));
+        AllExceptState {
+            inner,
+            excluded: excluded.into_boxed_slice(),
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn get_state(_: &Components) -> Option<Self::State> {
+        // TODO: fix this https://github.com/bevyengine/bevy/issues/13798
+        panic!(
+            "transmuting and any other operations concerning the state of a query are currently broken and shouldn't be used. See https://github.com/JoJoJet/bevy-trait-query/issues/59"
+        );
+    }
+
+    fn matches_component_set(
+        _state: &Self::State,
+        _set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        // Unlike `One`/`OneChanged`, an entity need not have any component implementing
+        // `Trait` at all: it is always a match, just like `All<&dyn Trait>`.
+        true
+    }
+
+    #[inline]
+    fn shrink_fetch<'wlong: 'wshort, 'wshort>(fetch: Self::Fetch<'wlong>) -> Self::Fetch<'wshort> {
+        fetch
+    }
+}
+
+unsafe impl<Trait: ?Sized + TraitQuery, B: Bundle> ReadOnlyQueryData for AllExcept<Trait, B> {}